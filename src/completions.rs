@@ -0,0 +1,70 @@
+//! Shell completions for the `esp-generate` CLI.
+//!
+//! `--chip` and `--option` get *dynamic* completion through clap_complete's completion engine:
+//! [`complete`] is called on every invocation and only takes over (printing candidates and
+//! exiting) when the shell is actually asking for completions, via the `COMPLETE` environment
+//! variable. `esp-generate completions <shell>` prints the matching registration script (the
+//! same thing `COMPLETE=<shell> esp-generate` would print) for the user to source from their
+//! shell's rc file; because that script calls back into this binary for every completion
+//! request rather than listing a fixed set of candidates, `-o`/`--chip` suggestions stay
+//! chip-aware the same way `remove_incompatible_chip_options` filters them for the TUI.
+
+use std::ffi::OsStr;
+
+use clap::{Command, CommandFactory, ValueEnum};
+use clap_complete::{
+    engine::{ArgValueCompleter, CompletionCandidate},
+    CompleteEnv, Shell,
+};
+use esp_metadata::Chip;
+use strum::IntoEnumIterator;
+
+use crate::{Args, TEMPLATE};
+
+/// Print the dynamic-completion registration script for `shell` to stdout.
+pub fn print(shell: Shell) {
+    // `CompleteEnv` reads this itself; setting it here lets us reuse the exact same
+    // registration-script output that `COMPLETE=<shell> esp-generate` would produce.
+    std::env::set_var("COMPLETE", shell.to_string());
+    CompleteEnv::with_factory(command).complete();
+}
+
+/// Hand control to the completion engine if the shell is currently requesting completions.
+/// Must run before `Args::parse()`, and does nothing otherwise.
+pub fn complete() {
+    CompleteEnv::with_factory(command).complete();
+}
+
+/// The `Args` command, augmented with dynamic completers for `--chip` and `--option`.
+fn command() -> Command {
+    Args::command()
+        .mut_arg("chip", |arg| arg.add(ArgValueCompleter::new(complete_chip)))
+        .mut_arg("option", |arg| {
+            arg.add(ArgValueCompleter::new(complete_option))
+        })
+}
+
+fn complete_chip(_current: &OsStr) -> Vec<CompletionCandidate> {
+    Chip::iter()
+        .map(|chip| CompletionCandidate::new(chip.to_string()))
+        .collect()
+}
+
+/// Only suggest options compatible with the `--chip` already typed on the command line,
+/// mirroring `remove_incompatible_chip_options`.
+fn complete_option(_current: &OsStr) -> Vec<CompletionCandidate> {
+    let chip = std::env::args()
+        .skip_while(|arg| arg != "--chip" && arg != "-c")
+        .nth(1)
+        .and_then(|value| Chip::from_str(&value, true).ok());
+
+    TEMPLATE
+        .all_options()
+        .into_iter()
+        .filter(|option| match chip {
+            Some(chip) => option.chips.is_empty() || option.chips.contains(&chip),
+            None => true,
+        })
+        .map(|option| CompletionCandidate::new(option.name.clone()))
+        .collect()
+}
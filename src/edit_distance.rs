@@ -0,0 +1,71 @@
+//! Levenshtein edit distance, used to suggest corrections for misspelled option names.
+//!
+//! Modeled after cargo's own command-name lookup: when a CLI argument doesn't match any
+//! known candidate, we find the closest one (if it's close enough to be a plausible typo)
+//! and suggest it instead of just failing.
+
+/// Compute the Levenshtein distance between `a` and `b`, case-insensitively.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.to_lowercase();
+    let b = b.to_lowercase();
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+
+    for (i, &a_byte) in a.iter().enumerate() {
+        cur[0] = i + 1;
+
+        for (j, &b_byte) in b.iter().enumerate() {
+            let cost = usize::from(a_byte != b_byte);
+            cur[j + 1] = (cur[j] + 1).min(prev[j + 1] + 1).min(prev[j] + cost);
+        }
+
+        std::mem::swap(&mut prev, &mut cur);
+    }
+
+    prev[b.len()]
+}
+
+/// Find the candidate closest to `input`, if any is within a reasonable edit distance.
+///
+/// Ties are broken in favor of the shortest candidate.
+pub fn closest<'a, I>(input: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let threshold = (input.len() / 3).max(2);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(input, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, candidate)| (*distance, candidate.len()))
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn finds_close_match() {
+        let candidates = ["defmt", "probe-rs", "wifi"];
+        assert_eq!(closest("DEFMT", candidates), Some("defmt"));
+        assert_eq!(closest("defmtt", candidates), Some("defmt"));
+        assert_eq!(closest("wify", candidates), Some("wifi"));
+    }
+
+    #[test]
+    fn rejects_distant_input() {
+        let candidates = ["defmt", "probe-rs", "wifi"];
+        assert_eq!(closest("completely-unrelated", candidates), None);
+    }
+
+    #[test]
+    fn prefers_shortest_on_tie() {
+        let candidates = ["ab", "abc"];
+        assert_eq!(closest("abx", candidates), Some("ab"));
+    }
+}
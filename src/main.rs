@@ -7,7 +7,7 @@ use std::{
 };
 
 use anyhow::{bail, Result};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use env_logger::{Builder, Env};
 use esp_generate::template::{GeneratorOptionItem, Template};
 use esp_generate::{
@@ -23,8 +23,15 @@ use taplo::formatter::Options;
 use crate::template_files::TEMPLATE_FILES;
 
 mod check;
+mod completions;
+mod edit_distance;
+mod presets;
+mod report;
 mod template_files;
 mod tui;
+mod vcs;
+
+use crate::vcs::VersionControl;
 
 static TEMPLATE: LazyLock<Template> = LazyLock::new(|| {
     serde_yaml::from_str(
@@ -39,6 +46,9 @@ static TEMPLATE: LazyLock<Template> = LazyLock::new(|| {
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
     /// Name of the project to generate
     name: Option<String>,
 
@@ -70,12 +80,47 @@ struct Args {
     #[arg(short = 'O', long)]
     output_path: Option<PathBuf>,
 
+    /// Version control system to initialize in the project directory
+    ///
+    /// Defaults to auto-detecting: git is used unless the project is already nested inside
+    /// an existing git/hg/pijul/fossil repository, in which case initialization is skipped.
+    #[arg(long, value_enum)]
+    vcs: Option<VersionControl>,
+
+    /// Named preset of generation options, loaded from `esp-generate.toml`
+    ///
+    /// Expands into the preset's option list before the usual validation runs. Explicit
+    /// `-o`/`--option` flags always win over a conflicting preset option.
+    #[arg(long)]
+    preset: Option<String>,
+
+    /// Run the full generation pipeline and report what would be created, without writing
+    /// anything to disk or invoking `cargo fmt`/VCS.
+    ///
+    /// Implies `--headless`, and requires `--chip` and the project name to also be given up
+    /// front, since this mode can't fall back to prompting for them.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Output format used by `--dry-run`
+    #[arg(long, value_enum, default_value_t = report::OutputFormat::Text)]
+    output_format: report::OutputFormat,
+
     /// Do not check for updates
     #[arg(short, long, global = true, action)]
     #[cfg(feature = "update-informer")]
     skip_update_check: bool,
 }
 
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate the completion script for
+        shell: clap_complete::Shell,
+    },
+}
+
 /// Check crates.io for a new version of the application
 #[cfg(feature = "update-informer")]
 fn check_for_update(name: &str, version: &str) {
@@ -116,12 +161,31 @@ fn setup_args_interactive(args: &mut Args) -> Result<()> {
 }
 
 fn main() -> Result<()> {
+    // Only takes over (and exits) when the shell is actually requesting completions.
+    completions::complete();
+
     Builder::from_env(Env::default().default_filter_or(log::LevelFilter::Info.as_str()))
         .format_target(false)
         .init();
 
     let mut args = Args::parse();
 
+    if let Some(Commands::Completions { shell }) = args.command {
+        completions::print(shell);
+        return Ok(());
+    }
+
+    if args.dry_run {
+        args.headless = true;
+
+        if args.chip.is_none() || args.name.is_none() {
+            bail!(
+                "--dry-run requires both --chip and a project name to be given up front, since \
+                 it runs non-interactively and can't prompt for them"
+            );
+        }
+    }
+
     // Only check for updates once the command-line arguments have been processed,
     // to avoid printing any update notifications when the help message is
     // displayed.
@@ -152,6 +216,14 @@ fn main() -> Result<()> {
         bail!("Directory already exists");
     }
 
+    if let Some(preset_name) = &args.preset {
+        let presets = presets::Presets::load()?;
+        let preset_options = presets
+            .get(preset_name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown preset '{preset_name}'"))?;
+        args.option = merge_preset_options(preset_options, &args.option);
+    }
+
     // Validate options. We pass the unmodified template to the function, so that it can tell
     // the user which options are not supported for the selected chip.
     process_options(&TEMPLATE, &args)?;
@@ -192,6 +264,10 @@ fn main() -> Result<()> {
         args.option.clone()
     };
 
+    // The user-facing option set, before we append the internal processing tokens
+    // (selection groups, chip name, architecture) that `process_file` relies on.
+    let user_selected = selected.clone();
+
     // Also add the active selection groups
     for idx in 0..selected.len() {
         let option = find_option(&selected[idx], &template.options).unwrap();
@@ -242,6 +318,20 @@ fn main() -> Result<()> {
     variables.push(("rust_target".to_string(), chip.target().to_string()));
 
     let project_dir = path.join(&name);
+
+    if args.dry_run {
+        report::run(
+            args.output_format,
+            &chip.to_string(),
+            &project_dir,
+            &user_selected,
+            &selected,
+            &variables,
+            template_files::TEMPLATE_FILES,
+        );
+        return Ok(());
+    }
+
     fs::create_dir(&project_dir)?;
 
     for &(file_path, contents) in template_files::TEMPLATE_FILES.iter() {
@@ -278,21 +368,54 @@ fn main() -> Result<()> {
     let formated = taplo::formatter::format(&input, format_options);
     fs::write(project_dir.join("Cargo.toml"), formated)?;
 
-    if should_initialize_git_repo(&project_dir) {
-        // Run git init:
-        Command::new("git")
-            .arg("init")
-            .current_dir(&project_dir)
-            .output()?;
-    } else {
-        log::warn!("Current directory is already in a git repository, skipping git initialization");
-    }
+    vcs::initialize(&project_dir, args.vcs);
 
     check::check(chip, selected.contains(&"probe-rs".to_string()), msrv);
 
     Ok(())
 }
 
+/// Merge a preset's options with the explicit `-o` flags, letting explicit flags win any
+/// selection-group conflict with the preset.
+fn merge_preset_options(preset_options: &[String], explicit: &[String]) -> Vec<String> {
+    merge_preset_options_with(preset_options, explicit, |option| {
+        find_option(option, &TEMPLATE.options).map(|option| option.selection_group.clone())
+    })
+}
+
+/// Core of [`merge_preset_options`], taking the selection-group lookup as a parameter so it
+/// can be unit-tested without needing a real [`Template`].
+///
+/// Note that only explicit-vs-preset conflicts are resolved here: two preset options that
+/// collide in the same selection group are both passed through unchanged, and are caught by
+/// `process_options`'s own same-selection-group validation instead.
+fn merge_preset_options_with(
+    preset_options: &[String],
+    explicit: &[String],
+    selection_group_of: impl Fn(&str) -> Option<String>,
+) -> Vec<String> {
+    let mut combined = explicit.to_vec();
+
+    for preset_option in preset_options {
+        if explicit.contains(preset_option) {
+            continue;
+        }
+
+        let selection_group = selection_group_of(preset_option).unwrap_or_default();
+
+        let conflicts_with_explicit = !selection_group.is_empty()
+            && explicit
+                .iter()
+                .any(|option| selection_group_of(option).as_deref() == Some(selection_group.as_str()));
+
+        if !conflicts_with_explicit {
+            combined.push(preset_option.clone());
+        }
+    }
+
+    combined
+}
+
 fn remove_incompatible_chip_options(chip: Chip, options: &mut Vec<GeneratorOptionItem>) {
     options.retain_mut(|opt| match opt {
         GeneratorOptionItem::Category(category) => {
@@ -568,7 +691,13 @@ fn process_options(template: &Template, args: &Args) -> Result<()> {
         }
 
         if !option_found {
-            log::error!("Unknown option '{option}'");
+            let candidates = all_options.iter().map(|item| item.name.as_str());
+            match edit_distance::closest(option, candidates) {
+                Some(suggestion) => {
+                    log::error!("Unknown option '{option}', did you mean '{suggestion}'?")
+                }
+                None => log::error!("Unknown option '{option}'"),
+            }
             success = false;
         } else if !option_found_for_chip {
             log::error!("Option '{option}' is not supported for chip {arg_chip}");
@@ -597,23 +726,6 @@ fn process_options(template: &Template, args: &Args) -> Result<()> {
     }
 }
 
-fn should_initialize_git_repo(mut path: &Path) -> bool {
-    loop {
-        let dotgit_path = path.join(".git");
-        if dotgit_path.exists() && dotgit_path.is_dir() {
-            return false;
-        }
-
-        if let Some(parent) = path.parent() {
-            path = parent;
-        } else {
-            break;
-        }
-    }
-
-    true
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -803,4 +915,62 @@ mod test {
             assert_eq!(expected, res.trim(), "options: {:?}", options);
         }
     }
+
+    fn group_lookup(groups: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> + '_ {
+        move |option| {
+            groups
+                .iter()
+                .find(|(name, _)| *name == option)
+                .map(|(_, group)| group.to_string())
+        }
+    }
+
+    #[test]
+    fn test_merge_preset_options_explicit_wins_over_same_group_preset_option() {
+        let groups = [("wifi", "connectivity"), ("ble", "connectivity")];
+
+        let merged = merge_preset_options_with(
+            &["ble".to_string()],
+            &["wifi".to_string()],
+            group_lookup(&groups),
+        );
+
+        assert_eq!(merged, vec!["wifi".to_string()]);
+    }
+
+    #[test]
+    fn test_merge_preset_options_preset_group_conflicts_pass_through() {
+        let groups = [("defmt", "logging"), ("log", "logging")];
+
+        let merged = merge_preset_options_with(
+            &["defmt".to_string(), "log".to_string()],
+            &[],
+            group_lookup(&groups),
+        );
+
+        // Both conflicting preset options are kept; `process_options` is responsible for
+        // rejecting them as a same-selection-group conflict.
+        assert_eq!(merged, vec!["defmt".to_string(), "log".to_string()]);
+    }
+
+    #[test]
+    fn test_merge_preset_options_unknown_option_passes_through() {
+        let merged = merge_preset_options_with(
+            &["not-a-real-option".to_string()],
+            &[],
+            group_lookup(&[]),
+        );
+
+        assert_eq!(merged, vec!["not-a-real-option".to_string()]);
+    }
+
+    #[test]
+    fn test_merge_preset_options_no_explicit_flags() {
+        let groups = [("probe-rs", "debugger")];
+
+        let merged =
+            merge_preset_options_with(&["probe-rs".to_string()], &[], group_lookup(&groups));
+
+        assert_eq!(merged, vec!["probe-rs".to_string()]);
+    }
 }
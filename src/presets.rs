@@ -0,0 +1,49 @@
+//! Named option presets loaded from an `esp-generate.toml` config file.
+//!
+//! Analogous to cargo's `[alias]` table: a preset maps a short label to a list of `-o`
+//! option names, so teams can standardize on a bundle of options (e.g. `wifi-defmt-probe-rs`)
+//! instead of re-typing long `-o` chains on every invocation.
+
+use std::{collections::HashMap, env, fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// Presets defined in an `esp-generate.toml` file, under a `[preset]` table.
+#[derive(Debug, Default, Deserialize)]
+pub struct Presets {
+    #[serde(default)]
+    preset: HashMap<String, Vec<String>>,
+}
+
+impl Presets {
+    /// Load presets from the first `esp-generate.toml` found in the working directory or
+    /// `$XDG_CONFIG_HOME/esp-generate/`. Returns an empty set of presets if neither exists.
+    pub fn load() -> Result<Presets> {
+        for path in Self::search_paths() {
+            if path.is_file() {
+                let contents = fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                return toml::from_str(&contents)
+                    .with_context(|| format!("Failed to parse {}", path.display()));
+            }
+        }
+
+        Ok(Presets::default())
+    }
+
+    fn search_paths() -> Vec<PathBuf> {
+        let mut paths = vec![PathBuf::from("esp-generate.toml")];
+
+        if let Some(config_home) = env::var_os("XDG_CONFIG_HOME") {
+            paths.push(PathBuf::from(config_home).join("esp-generate/esp-generate.toml"));
+        }
+
+        paths
+    }
+
+    /// The options that make up the named preset, if it exists.
+    pub fn get(&self, name: &str) -> Option<&[String]> {
+        self.preset.get(name).map(Vec::as_slice)
+    }
+}
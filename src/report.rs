@@ -0,0 +1,101 @@
+//! `--dry-run` reporting.
+//!
+//! Runs the same option validation and file processing as a real generation, but instead of
+//! creating directories, writing files, or invoking `cargo fmt`/VCS, prints what generation
+//! would have done. Supports scripted validation and previewing in CI.
+
+use std::{fmt, path::Path};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::process_file;
+
+/// How to print a `--dry-run` report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct FileReport {
+    path: String,
+    size: usize,
+}
+
+#[derive(Serialize)]
+struct DryRunReport {
+    chip: String,
+    options: Vec<String>,
+    variables: Vec<(String, String)>,
+    files: Vec<FileReport>,
+}
+
+/// Resolve every template file and report what would have been written under `project_dir`,
+/// without touching the filesystem.
+///
+/// `options` is the user-facing selected option set reported back to the caller; it does not
+/// include the internal processing tokens (selection groups, chip name, architecture) that
+/// `process_options` carries so that `process_file` can evaluate the template's `#IF`s. Keeping
+/// the two separate is what lets the JSON output serve as a stable contract describing what the
+/// user actually asked for.
+pub fn run(
+    format: OutputFormat,
+    chip: &str,
+    project_dir: &Path,
+    options: &[String],
+    process_options: &[String],
+    variables: &[(String, String)],
+    template_files: &[(&str, &str)],
+) {
+    let mut files = Vec::new();
+
+    for &(file_path, contents) in template_files {
+        let mut file_path = file_path.to_string();
+        if let Some(processed) = process_file(contents, process_options, variables, &mut file_path)
+        {
+            files.push(FileReport {
+                path: project_dir.join(&file_path).display().to_string(),
+                size: processed.len(),
+            });
+        }
+    }
+
+    let report = DryRunReport {
+        chip: chip.to_string(),
+        options: options.to_vec(),
+        variables: variables.to_vec(),
+        files,
+    };
+
+    match format {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).expect("report is always serializable")
+            );
+        }
+        OutputFormat::Text => {
+            println!("Chip: {}", report.chip);
+            println!("Options: {}", report.options.join(", "));
+            println!("Variables:");
+            for (key, value) in &report.variables {
+                println!("  {key} = {value}");
+            }
+            println!("Files that would be created:");
+            for file in &report.files {
+                println!("  {} ({} bytes)", file.path, file.size);
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Version control initialization for generated projects.
+//!
+//! Mirrors cargo's `NewOptions`/`VersionControl` model: the user can either let us
+//! auto-detect whatever VCS (if any) the project is already nested inside, or force a
+//! specific backend (including explicitly opting out) via `--vcs`.
+
+use std::{fmt, fs, path::Path, process::Command};
+
+use clap::ValueEnum;
+
+/// Version control system to initialize in the generated project directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum VersionControl {
+    Git,
+    Hg,
+    Pijul,
+    Fossil,
+    /// Do not initialize any version control system.
+    None,
+}
+
+impl fmt::Display for VersionControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            VersionControl::Git => "git",
+            VersionControl::Hg => "hg",
+            VersionControl::Pijul => "pijul",
+            VersionControl::Fossil => "fossil",
+            VersionControl::None => "none",
+        })
+    }
+}
+
+impl VersionControl {
+    /// Name of the ignore file this backend reads, if it uses one in `.gitignore` format.
+    fn ignore_file_name(self) -> Option<&'static str> {
+        match self {
+            VersionControl::Git => Some(".gitignore"),
+            VersionControl::Hg => Some(".hgignore"),
+            VersionControl::Pijul | VersionControl::Fossil | VersionControl::None => None,
+        }
+    }
+
+    /// Run this backend's init command inside `project_dir`, and for Mercurial also write an
+    /// `.hgignore` derived from the template's `.gitignore`.
+    fn init(self, project_dir: &Path) {
+        let (command, init_args) = match self {
+            VersionControl::Git => ("git", ["init"].as_slice()),
+            VersionControl::Hg => ("hg", ["init"].as_slice()),
+            VersionControl::Pijul => ("pijul", ["init"].as_slice()),
+            VersionControl::Fossil => ("fossil", ["init", ".fossil"].as_slice()),
+            VersionControl::None => return,
+        };
+
+        match Command::new(command)
+            .args(init_args)
+            .current_dir(project_dir)
+            .output()
+        {
+            Ok(output) if !output.status.success() => {
+                log::warn!(
+                    "`{command} {}` exited with {}, skipping VCS initialization",
+                    init_args.join(" "),
+                    output.status
+                );
+                return;
+            }
+            Err(err) => {
+                log::warn!("Failed to run `{command} {}`: {err}", init_args.join(" "));
+                return;
+            }
+            Ok(_) => {}
+        }
+
+        // The template already ships a `.gitignore`; for Mercurial we derive `.hgignore` from
+        // it instead of duplicating the patterns in the template. Pijul and Fossil get no
+        // ignore file of their own.
+        if let Some(ignore_file) = self.ignore_file_name() {
+            if ignore_file != ".gitignore" {
+                if let Ok(gitignore) = fs::read_to_string(project_dir.join(".gitignore")) {
+                    let contents = match self {
+                        // `.hgignore` defaults to regexp syntax, not gitignore-style globs, so
+                        // tell Mercurial to read the patterns as globs instead.
+                        VersionControl::Hg => format!("syntax: glob\n{gitignore}"),
+                        _ => gitignore,
+                    };
+
+                    if let Err(err) = fs::write(project_dir.join(ignore_file), contents) {
+                        log::warn!("Failed to write {ignore_file}: {err}");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Walk up from `path` looking for a marker of an existing VCS checkout, so that we never
+/// nest repositories.
+pub fn existing_vcs_repo(mut path: &Path) -> Option<VersionControl> {
+    loop {
+        if path.join(".git").is_dir() {
+            return Some(VersionControl::Git);
+        }
+        if path.join(".hg").is_dir() {
+            return Some(VersionControl::Hg);
+        }
+        if path.join(".pijul").is_dir() {
+            return Some(VersionControl::Pijul);
+        }
+        if path.join(".fossil-settings").exists() || path.join(".fslckout").exists() {
+            return Some(VersionControl::Fossil);
+        }
+
+        match path.parent() {
+            Some(parent) => path = parent,
+            None => return None,
+        }
+    }
+}
+
+/// Initialize the requested VCS (or auto-detect one) in `project_dir`.
+///
+/// `requested` is `None` when the user didn't pass `--vcs`, meaning we auto-detect: fall back
+/// to git unless `project_dir` is already nested inside an existing repository. An explicit
+/// `--vcs` is an instruction, not a hint, so it initializes regardless of nesting.
+pub fn initialize(project_dir: &Path, requested: Option<VersionControl>) {
+    let Some(requested) = requested else {
+        if let Some(existing) = existing_vcs_repo(project_dir) {
+            log::warn!(
+                "Current directory is already in a {existing} repository, skipping VCS initialization"
+            );
+            return;
+        }
+
+        VersionControl::Git.init(project_dir);
+        return;
+    };
+
+    requested.init(project_dir);
+}